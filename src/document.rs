@@ -14,6 +14,7 @@ use std::{
     convert::Infallible,
     fmt::Debug,
     hash::{Hash, Hasher},
+    io::IsTerminal,
 };
 
 #[derive(Clone, Debug)]
@@ -45,22 +46,55 @@ impl Default for RouteDocumentation {
         }
     }
 }
+// The entries' own `PartialEq` only compares what identifies them, such as
+// a header's name, so routes compare every field of their entries instead.
+impl PartialEq for RouteDocumentation {
+    fn eq(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.path == other.path
+            && self.description == other.description
+            && self.tags == other.tags
+            && self.parameters == other.parameters
+            && self.queries == other.queries
+            && same_set(&self.bodies, &other.bodies, DocumentedBody::same)
+            && same_set(&self.cookies, &other.cookies, DocumentedCookie::same)
+            && same_set(&self.headers, &other.headers, DocumentedHeader::same)
+            && same_set(&self.responses, &other.responses, DocumentedResponse::same)
+    }
+}
+
+fn same_set<T: Eq + Hash>(a: &HashSet<T>, b: &HashSet<T>, same: fn(&T, &T) -> bool) -> bool {
+    a.len() == b.len() && a.iter().all(|x| b.get(x).into_iter().any(|y| same(x, y)))
+}
+
+// Adding an entry with a name that's already documented merges the two, so a
+// route never documents the same name twice. The methods named after each
+// entry are for explicit documentation, so values set on the newer entry win.
+// The `upsert_*` methods are for the defaults that filters document, so they
+// only fill in what's missing from an existing entry.
 impl RouteDocumentation {
+    /// Adds the body, replacing any body with the same mime type.
     pub fn body<B: Into<DocumentedBody>>(&mut self, body: B) {
-        self.bodies.insert(body.into());
+        self.bodies.replace(body.into());
     }
+    /// Adds the cookie, overriding any cookie with the same name.
     pub fn cookie(&mut self, cookie: DocumentedCookie) {
-        self.cookies.insert(cookie);
+        merge_set(&mut self.cookies, cookie, DocumentedCookie::merge);
     }
     pub fn description<S: Into<String>>(&mut self, description: S) {
         self.description = Some(description.into());
     }
+    /// Adds the header, overriding any header with the same name.
     pub fn header(&mut self, header: DocumentedHeader) {
-        self.headers.insert(header);
+        merge_set(&mut self.headers, header, DocumentedHeader::merge);
     }
+    /// Adds a path segment for the parameter, overriding any parameter with
+    /// the same name.
+    ///
+    /// If a parameter with the same name exists, the segment refers to it instead.
     pub fn parameter(&mut self, parameter: DocumentedParameter) {
-        self.push_path(format!("{{{}}}", self.parameters.len()));
-        self.parameters.push(parameter);
+        let index = merge_list(&mut self.parameters, parameter, DocumentedParameter::merge);
+        self.push_path(format!("{{{}}}", index));
     }
     /// The path but with the path parameters having the same name as the parameters instead of index values.
     pub fn pretty_path(&self) -> String {
@@ -80,15 +114,97 @@ impl RouteDocumentation {
         }
         self.path.push_str(path.as_ref());
     }
+    /// Adds the query, overriding any query with the same name.
     pub fn query(&mut self, query: DocumentedQuery) {
-        self.queries.push(query);
+        merge_list(&mut self.queries, query, DocumentedQuery::merge);
     }
+    /// Adds the response, overriding any response with the same status.
     pub fn response<R: Into<DocumentedResponse>>(&mut self, response: R) {
-        self.responses.insert(response.into());
+        merge_set(
+            &mut self.responses,
+            response.into(),
+            DocumentedResponse::merge,
+        );
     }
     pub fn tag<T: Into<String>>(&mut self, tag: T) {
         self.tags.push(tag.into());
     }
+    /// Adds the body, unless there's already a body with the same mime type.
+    pub fn upsert_body(&mut self, body: DocumentedBody) {
+        if !self.bodies.contains(&body) {
+            self.bodies.insert(body);
+        }
+    }
+    /// Adds the cookie, or fills in what's missing from a cookie with the same name.
+    pub fn upsert_cookie(&mut self, cookie: DocumentedCookie) {
+        merge_set(&mut self.cookies, cookie, |existing, default| {
+            default.merge(existing)
+        });
+    }
+    /// Adds the header, or fills in what's missing from a header with the same name.
+    pub fn upsert_header(&mut self, header: DocumentedHeader) {
+        merge_set(&mut self.headers, header, |existing, default| {
+            default.merge(existing)
+        });
+    }
+    /// Adds a path segment for the parameter, or for a parameter with the same
+    /// name after filling in what's missing from it.
+    pub fn upsert_parameter(&mut self, parameter: DocumentedParameter) {
+        let index = merge_list(&mut self.parameters, parameter, |existing, default| {
+            default.merge(existing)
+        });
+        self.push_path(format!("{{{}}}", index));
+    }
+    /// Adds the query, or fills in what's missing from a query with the same name.
+    pub fn upsert_query(&mut self, query: DocumentedQuery) {
+        merge_list(&mut self.queries, query, |existing, default| {
+            default.merge(existing)
+        });
+    }
+    /// Adds the response, or fills in what's missing from a response with the
+    /// same status.
+    pub fn upsert_response(&mut self, response: DocumentedResponse) {
+        merge_set(&mut self.responses, response, |existing, default| {
+            default.merge(existing)
+        });
+    }
+}
+
+fn merge_set<T: Eq + Hash>(set: &mut HashSet<T>, item: T, merge: fn(T, T) -> T) {
+    let item = match set.take(&item) {
+        Some(existing) => merge(existing, item),
+        None => item,
+    };
+    set.insert(item);
+}
+
+// Returns the index of the merged item.
+fn merge_list<T: Named>(list: &mut Vec<T>, item: T, merge: fn(T, T) -> T) -> usize {
+    match list.iter().position(|x| x.name() == item.name()) {
+        Some(index) => {
+            let existing = list.remove(index);
+            list.insert(index, merge(existing, item));
+            index
+        }
+        None => {
+            list.push(item);
+            list.len() - 1
+        }
+    }
+}
+
+trait Named {
+    fn name(&self) -> &str;
+}
+impl Named for DocumentedParameter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+impl Named for DocumentedQuery {
+    fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -113,6 +229,18 @@ impl DocumentedCookie {
         self.required = required;
         self
     }
+    fn merge(self, newer: Self) -> Self {
+        Self {
+            name: self.name,
+            description: newer.description.or(self.description),
+            required: self.required || newer.required,
+        }
+    }
+    fn same(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.required == other.required
+    }
 }
 impl Hash for DocumentedCookie {
     fn hash<H: Hasher>(&self, hasher: &mut H) {
@@ -148,6 +276,18 @@ impl DocumentedHeader {
         self.required = required;
         self
     }
+    fn merge(self, newer: Self) -> Self {
+        Self {
+            name: self.name,
+            description: newer.description.or(self.description),
+            required: self.required || newer.required,
+        }
+    }
+    fn same(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.required == other.required
+    }
 }
 impl Hash for DocumentedHeader {
     fn hash<H: Hasher>(&self, hasher: &mut H) {
@@ -161,7 +301,7 @@ impl PartialEq<Self> for DocumentedHeader {
 }
 impl Eq for DocumentedHeader {}
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DocumentedParameter {
     pub name: String,
     pub description: Option<String>,
@@ -188,9 +328,17 @@ impl DocumentedParameter {
         self.required = required;
         self
     }
+    fn merge(self, newer: Self) -> Self {
+        Self {
+            name: self.name,
+            description: newer.description.or(self.description),
+            type_: newer.type_,
+            required: self.required || newer.required,
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DocumentedQuery {
     pub name: String,
     pub description: Option<String>,
@@ -214,6 +362,14 @@ impl DocumentedQuery {
         self.required = required;
         self
     }
+    fn merge(self, newer: Self) -> Self {
+        Self {
+            name: self.name,
+            description: newer.description.or(self.description),
+            type_: newer.type_,
+            required: self.required || newer.required,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Eq)]
@@ -229,10 +385,15 @@ impl DocumentedResponse {
         self
     }
     pub fn body(mut self, body: DocumentedBody) -> Self {
-        self.body.insert(body);
+        self.body.replace(body);
         self
     }
+    /// Adds the header, merging it into any header with the same name.
     pub fn header(mut self, header: DocumentedHeader) -> Self {
+        let header = match self.headers.take(&header) {
+            Some(existing) => existing.merge(header),
+            None => header,
+        };
         self.headers.insert(header);
         self
     }
@@ -240,6 +401,25 @@ impl DocumentedResponse {
         self.status = status;
         self
     }
+    fn merge(self, newer: Self) -> Self {
+        let description = if newer.description.is_empty() {
+            self.description
+        } else {
+            newer.description
+        };
+        let merged = Self {
+            description,
+            ..self
+        };
+        let merged = newer.body.into_iter().fold(merged, Self::body);
+        newer.headers.into_iter().fold(merged, Self::header)
+    }
+    fn same(&self, other: &Self) -> bool {
+        self.status == other.status
+            && self.description == other.description
+            && same_set(&self.body, &other.body, DocumentedBody::same)
+            && same_set(&self.headers, &other.headers, DocumentedHeader::same)
+    }
 }
 impl Hash for DocumentedResponse {
     fn hash<H: Hasher>(&self, hasher: &mut H) {
@@ -279,6 +459,9 @@ impl DocumentedBody {
         self.mime = Some(mime.into());
         self
     }
+    fn same(&self, other: &Self) -> bool {
+        self.mime == other.mime && self.body == other.body
+    }
 }
 impl Documentable for DocumentedBody {
     fn document(&self, route: &mut RouteDocumentation) {
@@ -362,7 +545,7 @@ pub fn map<T: Into<Box<DocumentedType>>>(value_type: T) -> DocumentedType {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum DocumentedType {
     Array {
         ty: Box<DocumentedType>,
@@ -434,7 +617,7 @@ impl From<HashMap<String, DocumentedType>> for DocumentedType {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum InternalDocumentedType {
     Boolean,
     Float,
@@ -515,10 +698,20 @@ impl From<TypeId> for DocumentedType {
     }
 }
 
+/// Describes every route the filter can match.
+///
+/// The filter is only borrowed, so it can still be served afterwards, and
+/// describing it again returns the same routes in the same order. For this to
+/// hold, the callbacks given to [`explicit`] and [`document`] should be
+/// idempotent: they run on every call and shouldn't depend on state that
+/// changes between calls, like a counter.
 pub fn describe<F: Filter>(filter: &F) -> Vec<RouteDocumentation> {
     filter.describe(RouteDocumentation::default())
 }
 
+/// Wraps a filter, documenting it with `describe` instead of the filter's own documentation.
+///
+/// `describe` is called every time the route is described, so it should be idempotent.
 pub fn explicit<F, D>(filter: F, describe: D) -> ExplicitDocumentation<F, D>
 where
     F: Filter,
@@ -625,6 +818,144 @@ pub fn tail(
     })
 }
 
+/// A one line summary of a route, as listed by [`print_routes`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteSummary {
+    pub method: String,
+    pub path: String,
+    pub summary: Option<String>,
+    pub tags: Vec<String>,
+}
+impl From<&RouteDocumentation> for RouteSummary {
+    fn from(route: &RouteDocumentation) -> Self {
+        Self {
+            method: route.method.to_string(),
+            path: route.pretty_path(),
+            summary: route
+                .description
+                .as_ref()
+                .and_then(|description| description.lines().next())
+                .map(|line| line.trim().to_owned())
+                .filter(|line| !line.is_empty()),
+            tags: route.tags.clone(),
+        }
+    }
+}
+
+/// Summarizes every route the filter can match, in the same order as [`describe`].
+pub fn summarize<F: Filter>(filter: &F) -> Vec<RouteSummary> {
+    describe(filter).iter().map(RouteSummary::from).collect()
+}
+
+/// Summarizes every route the filter can match as a JSON array.
+///
+/// Each route is an object with `method`, `path`, `summary` and `tags` fields.
+pub fn routes_json<F: Filter>(filter: &F) -> Value {
+    summarize(filter)
+        .into_iter()
+        .map(|route| {
+            serde_json::json!({
+                "method": route.method,
+                "path": route.path,
+                "summary": route.summary,
+                "tags": route.tags,
+            })
+        })
+        .collect()
+}
+
+/// Formats the routes as a table, with a column each for the method, path,
+/// summary and tags.
+///
+/// If `color` is set, the methods are colored with ANSI escape codes.
+pub fn routes_table(routes: &[RouteSummary], color: bool) -> String {
+    const HEADINGS: [&str; 4] = ["METHOD", "PATH", "SUMMARY", "TAGS"];
+
+    let rows = routes
+        .iter()
+        .map(|route| {
+            [
+                route.method.clone(),
+                route.path.clone(),
+                route.summary.clone().unwrap_or_default(),
+                route.tags.join(", "),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut widths = HEADINGS
+        .iter()
+        .map(|heading| heading.len())
+        .collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    push_row(&mut table, &HEADINGS, &widths, None);
+    for row in &rows {
+        let code = if color {
+            Some(method_color(&row[0]))
+        } else {
+            None
+        };
+        push_row(&mut table, row, &widths, code);
+    }
+    table
+}
+
+fn push_row<S: AsRef<str>>(table: &mut String, cells: &[S], widths: &[usize], code: Option<&str>) {
+    let mut line = String::new();
+    for (i, (cell, width)) in cells.iter().zip(widths).enumerate() {
+        let cell = cell.as_ref();
+        if i > 0 {
+            line.push_str("  ");
+        }
+        match code {
+            Some(code) if i == 0 => line.push_str(&format!("\x1b[{}m{}\x1b[0m", code, cell)),
+            _ => line.push_str(cell),
+        }
+        // Padded by hand, as `format!` would count the escape codes towards the width.
+        line.push_str(&" ".repeat(width - cell.chars().count()));
+    }
+    table.push_str(line.trim_end());
+    table.push('\n');
+}
+
+fn method_color(method: &str) -> &'static str {
+    match method {
+        "GET" => "32",
+        "POST" => "33",
+        "PUT" | "PATCH" => "34",
+        "DELETE" => "31",
+        _ => "36",
+    }
+}
+
+/// Prints a table of every route the filter can match to stdout, like
+/// `rake routes`.
+///
+/// The methods are colored if stdout is a terminal. Use [`routes_json`] for
+/// the same information in a structured format.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+///
+/// let hello = warp::get()
+///     .and(warp::path("hello"))
+///     .and(warp::document::document(warp::document::description("Say hello")))
+///     .map(warp::reply);
+///
+/// warp::document::print_routes(&hello);
+/// ```
+pub fn print_routes<F: Filter>(filter: &F) {
+    let color = std::io::stdout().is_terminal();
+    print!("{}", routes_table(&summarize(filter), color));
+}
+
 #[cfg(feature = "openapi")]
 pub fn to_openapi<I: IntoIterator<Item = RouteDocumentation>>(routes: I) -> openapiv3::OpenAPI {
     use indexmap::IndexMap;
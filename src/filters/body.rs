@@ -65,12 +65,12 @@ pub fn content_length_limit(limit: u64) -> impl Filter<Extract = (), Error = Rej
         })
         .untuple_one();
     document::explicit(filter, move |route| {
-        route.header(
+        route.upsert_header(
             document::header("content-length")
                 .description(format!("Must be a value below {} bytes.", limit))
-                .required(true)
+                .required(true),
         );
-        route.response(document::response(413, None)
+        route.upsert_response(document::response(413, None)
             .description("`content-length` header is missing, is invalid, or has a number larger than the limit provided."))
     })
 }
@@ -21,8 +21,8 @@ pub fn cookie(name: &'static str) -> impl Filter<Extract = One<String>, Error =
         future::ready(cookie)
     });
     document::explicit(filter, move |route| {
-        route.response(document::response(400, None).description("Bad Response"));
-        route.cookie(document::cookie(name).required(true));
+        route.upsert_response(document::response(400, None).description("Bad Response"));
+        route.upsert_cookie(document::cookie(name).required(true));
     })
 }
 
@@ -36,6 +36,6 @@ pub fn optional(
     let filter = header::optional2()
         .map(move |opt: Option<Cookie>| opt.and_then(|cookie| cookie.get(name).map(String::from)));
     document::explicit(filter, move |route| {
-        route.cookie(document::cookie(name).required(false));
+        route.upsert_cookie(document::cookie(name).required(false));
     })
 }
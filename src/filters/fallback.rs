@@ -0,0 +1,168 @@
+//! Fallback Filters
+//!
+//! A fallback is meant to be the last branch of a chain of `or()`s, replying
+//! with a custom `404 Not Found` to requests that no other route matched.
+
+use std::any::TypeId;
+use std::fmt;
+use std::sync::Arc;
+
+use futures::future;
+use http::StatusCode;
+
+use crate::document::{self, RouteDocumentation};
+use crate::filter::{FilterBase, Internal, One};
+use crate::reject::{self, Rejection};
+use crate::reply::{Reply, Response};
+
+/// Creates a `Filter` that replies to any remaining request with `reply`, as a
+/// `404 Not Found`.
+///
+/// This should be used as the last branch of `or()`. Rather than matching
+/// directly, it rejects with a `404 Not Found` that is rendered using `reply`.
+/// This means better rejections from earlier branches still win, such as a
+/// `405 Method Not Allowed` when a route only exists for another method.
+///
+/// The status of the response is always `404 Not Found`.
+///
+/// # Filter Order
+///
+/// For a `405` to only be returned when a route's path matched, the routes
+/// must check the path *before* the method. Otherwise, a method filter like
+/// `warp::get()` rejects every request with another method as a `405`, no
+/// matter the path, and the fallback is never rendered:
+///
+/// ```
+/// use warp::Filter;
+///
+/// // A `POST /nope` is a `405`, not the fallback.
+/// let wrong = warp::get().and(warp::path("hello"));
+/// // A `POST /nope` is the fallback, and a `POST /hello` is a `405`.
+/// let right = warp::path("hello").and(warp::get());
+/// ```
+///
+/// # Recovering
+///
+/// The rejection isn't a plain `404`, so [`Rejection::is_not_found`] returns
+/// `false` for it. A `recover` handler can instead look for a
+/// [`FallbackNotFound`] with [`Rejection::find`].
+///
+/// # Documentation
+///
+/// A fallback isn't a real route, so it's left out of the documentation by
+/// default. Use [`Fallback::documented`] to document it as a `GET` with a
+/// `{fallback_path}` parameter for the rest of the path, that only responds
+/// with `404`.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+///
+/// let hello = warp::path("hello")
+///     .and(warp::path::end())
+///     .and(warp::get())
+///     .map(|| "Hello, World!");
+///
+/// let routes = hello.or(warp::filters::fallback(|| {
+///     warp::reply::html("<h1>Nothing to see here</h1>")
+/// }));
+/// ```
+pub fn fallback<F, R>(reply: F) -> Fallback
+where
+    F: Fn() -> R + Send + Sync + 'static,
+    R: Reply,
+{
+    Fallback {
+        reply: Arc::new(move || reply().into_response()),
+        documented: false,
+    }
+}
+
+/// A `Filter` replying with a custom `404 Not Found`.
+///
+/// Constructed from [`fallback`](fallback()).
+#[derive(Clone)]
+pub struct Fallback {
+    reply: Arc<dyn Fn() -> Response + Send + Sync>,
+    documented: bool,
+}
+
+impl Fallback {
+    /// Include this fallback in the route documentation.
+    pub fn documented(mut self) -> Self {
+        self.documented = true;
+        self
+    }
+}
+
+impl fmt::Debug for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Fallback")
+            .field("documented", &self.documented)
+            .finish()
+    }
+}
+
+impl FilterBase for Fallback {
+    type Extract = One<Response>;
+    type Error = Rejection;
+    type Future = future::Ready<Result<Self::Extract, Self::Error>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        future::err(reject::known(FallbackNotFound {
+            reply: self.reply.clone(),
+        }))
+    }
+
+    fn describe(&self, mut route: RouteDocumentation) -> Vec<RouteDocumentation> {
+        if !self.documented {
+            return Vec::new();
+        }
+        // OpenAPI has no wildcards, so the rest of the path is documented as a
+        // parameter, which keeps it from clashing with a route at this path.
+        route.method = http::Method::GET;
+        route.description(
+            "Fallback for requests that no other route matched. \
+             This is a wildcard matching any method, and any path below this one.",
+        );
+        route.upsert_parameter(
+            document::parameter("fallback_path", TypeId::of::<String>())
+                .description("The rest of a path not matched by another route."),
+        );
+        route.upsert_response(document::response(404, None).description("Not Found"));
+        vec![route]
+    }
+}
+
+// ===== Rejections =====
+
+/// An error used to reject requests that reached a [`fallback`](fallback()).
+///
+/// It is rendered as the fallback's reply when not recovered.
+pub struct FallbackNotFound {
+    reply: Arc<dyn Fn() -> Response + Send + Sync>,
+}
+
+impl FallbackNotFound {
+    /// Renders the fallback's reply, with a `404 Not Found` status.
+    pub fn response(&self) -> Response {
+        let mut res = (self.reply)();
+        *res.status_mut() = StatusCode::NOT_FOUND;
+        res
+    }
+}
+
+impl fmt::Debug for FallbackNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("FallbackNotFound")
+    }
+}
+
+impl fmt::Display for FallbackNotFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("No route matched the request")
+    }
+}
+
+impl ::std::error::Error for FallbackNotFound {}
@@ -48,7 +48,7 @@ pub fn header<T: FromStr + Send + 'static>(
         future::ready(route)
     });
     document::explicit(filter, move |route| {
-        route.header(document::header(name).required(true))
+        route.upsert_header(document::header(name).required(true))
     })
 }
 
@@ -99,7 +99,7 @@ where
         }
     });
     document::explicit(filter, move |route| {
-        route.header(document::header(name).required(false))
+        route.upsert_header(document::header(name).required(false))
     })
 }
 
@@ -160,7 +160,11 @@ pub fn exact(
         future::ready(route)
     });
     document::explicit(filter, move |route| {
-        route.header(document::header(name).description(format!("Must be set to `{}`.", value)).required(true))
+        route.upsert_header(
+            document::header(name)
+                .description(format!("Must be set to `{}`.", value))
+                .required(true),
+        )
     })
 }
 
@@ -195,7 +199,11 @@ pub fn exact_ignore_case(
         future::ready(route)
     });
     document::explicit(filter, move |route| {
-        route.header(document::header(name).description(format!("Must be set to `{}` (case insensitive).", value)).required(true))
+        route.upsert_header(
+            document::header(name)
+                .description(format!("Must be set to `{}` (case insensitive).", value))
+                .required(true),
+        )
     })
 }
 
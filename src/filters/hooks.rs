@@ -0,0 +1,372 @@
+//! Lifecycle Hook Filters
+//!
+//! These wrap a filter to be told when each request starts, and how it
+//! finished. Unlike the [`log`](../log/index.html) filters, the response hook
+//! waits for the response body to be handed to the server, and is also called
+//! when the client goes away first.
+
+use std::time::{Duration, Instant};
+
+use http::{self, StatusCode};
+
+use crate::filter::{Filter, WrapSealed};
+use crate::reject::IsReject;
+use crate::reply::Reply;
+use crate::route::Route;
+
+use self::internal::WithHooks;
+
+/// Create a wrapping filter that calls `on_request` when a request starts,
+/// and `on_response` when it is finished.
+///
+/// The value returned by `on_request` is passed to `on_response`, which is
+/// called exactly once per request, with a [`Disposition`] of:
+///
+/// - `Completed` once the last of the response body has been handed to the
+///   server. It may still be buffered, rather than written to the client.
+/// - `Rejected` if the wrapped filter rejected the request.
+/// - `Aborted` if the request was dropped first, such as when the client
+///   disconnects while the body is still being sent.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use warp::Filter;
+/// use warp::filters::hooks::{RequestInfo, ResponseInfo};
+///
+/// let in_flight = Arc::new(AtomicUsize::new(0));
+/// let started = in_flight.clone();
+///
+/// let hooks = warp::filters::hooks(
+///     move |_: RequestInfo| {
+///         started.fetch_add(1, Ordering::SeqCst);
+///     },
+///     move |_, info: ResponseInfo| {
+///         in_flight.fetch_sub(1, Ordering::SeqCst);
+///         eprintln!("{:?} {:?} in {:?}", info.disposition(), info.status(), info.elapsed());
+///     },
+/// );
+/// let route = warp::any()
+///     .map(warp::reply)
+///     .with(hooks);
+/// ```
+pub fn hooks<Req, Res, T>(on_request: Req, on_response: Res) -> Hooks<Req, Res>
+where
+    Req: Fn(RequestInfo) -> T,
+    Res: Fn(T, ResponseInfo),
+{
+    Hooks {
+        on_request,
+        on_response,
+    }
+}
+
+/// Decorates a [`Filter`](crate::Filter) to call hooks when requests start and finish.
+#[derive(Clone, Copy, Debug)]
+pub struct Hooks<Req, Res> {
+    on_request: Req,
+    on_response: Res,
+}
+
+/// Information about the request, given to the `on_request` hook.
+#[allow(missing_debug_implementations)]
+pub struct RequestInfo<'a> {
+    route: &'a Route,
+}
+
+impl<'a> RequestInfo<'a> {
+    /// View the `http::Method` of the request.
+    pub fn method(&self) -> &http::Method {
+        self.route.method()
+    }
+
+    /// View the URI path of the request.
+    pub fn path(&self) -> &str {
+        self.route.full_path()
+    }
+
+    /// Access the full headers of the request.
+    pub fn headers(&self) -> &http::HeaderMap {
+        self.route.headers()
+    }
+}
+
+/// How a request finished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Disposition {
+    /// The whole response was handed to the server to be written.
+    Completed,
+    /// The wrapped filter rejected the request.
+    Rejected,
+    /// The request was dropped before the response was sent.
+    Aborted,
+}
+
+/// Information about how the request finished, given to the `on_response` hook.
+#[derive(Debug)]
+pub struct ResponseInfo {
+    disposition: Disposition,
+    elapsed: Duration,
+    status: Option<StatusCode>,
+}
+
+impl ResponseInfo {
+    /// View how the request finished.
+    pub fn disposition(&self) -> Disposition {
+        self.disposition
+    }
+
+    /// View the `Duration` that elapsed since the request started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// View the `http::StatusCode` of the response.
+    ///
+    /// This is `None` if the request was aborted before a response was made.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+}
+
+impl<Req, Res, T, F> WrapSealed<F> for Hooks<Req, Res>
+where
+    Req: Fn(RequestInfo) -> T + Clone + Send,
+    Res: Fn(T, ResponseInfo) + Clone + Send + 'static,
+    T: Send + 'static,
+    F: Filter + Clone + Send,
+    F::Extract: Reply,
+    F::Error: IsReject,
+{
+    type Wrapped = WithHooks<Req, Res, F>;
+
+    fn wrap(&self, filter: F) -> Self::Wrapped {
+        WithHooks {
+            filter,
+            hooks: self.clone(),
+        }
+    }
+}
+
+// Calls `on_response` when finished, or with `Aborted` if dropped first.
+struct Guard<T, Res>
+where
+    Res: Fn(T, ResponseInfo),
+{
+    token: Option<T>,
+    on_response: Res,
+    started: Instant,
+    status: Option<StatusCode>,
+}
+
+impl<T, Res> Guard<T, Res>
+where
+    Res: Fn(T, ResponseInfo),
+{
+    fn finish(&mut self, disposition: Disposition) {
+        if let Some(token) = self.token.take() {
+            (self.on_response)(
+                token,
+                ResponseInfo {
+                    disposition,
+                    elapsed: Instant::now() - self.started,
+                    status: self.status,
+                },
+            );
+        }
+    }
+}
+
+impl<T, Res> Drop for Guard<T, Res>
+where
+    Res: Fn(T, ResponseInfo),
+{
+    fn drop(&mut self) {
+        self.finish(Disposition::Aborted);
+    }
+}
+
+mod internal {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::Instant;
+
+    use bytes::Bytes;
+    use futures::{ready, Stream, TryFuture};
+    use http::header::CONTENT_LENGTH;
+    use http::{Method, StatusCode};
+    use hyper::body::HttpBody;
+    use hyper::Body;
+    use pin_project::pin_project;
+
+    use super::{Disposition, Guard, Hooks, RequestInfo, ResponseInfo};
+    use crate::filter::{Filter, FilterBase, Internal};
+    use crate::reject::IsReject;
+    use crate::reply::{Reply, Response};
+    use crate::route;
+
+    #[allow(missing_debug_implementations)]
+    pub struct Hooked(Response);
+
+    impl Reply for Hooked {
+        #[inline]
+        fn into_response(self) -> Response {
+            self.0
+        }
+    }
+
+    #[allow(missing_debug_implementations)]
+    #[derive(Clone, Copy)]
+    pub struct WithHooks<Req, Res, F> {
+        pub(super) filter: F,
+        pub(super) hooks: Hooks<Req, Res>,
+    }
+
+    impl<Req, Res, T, F> FilterBase for WithHooks<Req, Res, F>
+    where
+        Req: Fn(RequestInfo) -> T + Clone + Send,
+        Res: Fn(T, ResponseInfo) + Clone + Send + 'static,
+        T: Send + 'static,
+        F: Filter + Clone + Send,
+        F::Extract: Reply,
+        F::Error: IsReject,
+    {
+        type Extract = (Hooked,);
+        type Error = F::Error;
+        type Future = WithHooksFuture<T, Res, F::Future>;
+
+        fn filter(&self, _: Internal) -> Self::Future {
+            let started = Instant::now();
+            let (token, head) = route::with(|route| {
+                let head = route.method() == Method::HEAD;
+                ((self.hooks.on_request)(RequestInfo { route }), head)
+            });
+            WithHooksFuture {
+                head,
+                guard: Some(Guard {
+                    token: Some(token),
+                    on_response: self.hooks.on_response.clone(),
+                    started,
+                    status: None,
+                }),
+                future: self.filter.filter(Internal),
+            }
+        }
+    }
+
+    #[allow(missing_debug_implementations)]
+    #[pin_project]
+    pub struct WithHooksFuture<T, Res, F>
+    where
+        Res: Fn(T, ResponseInfo),
+    {
+        head: bool,
+        guard: Option<Guard<T, Res>>,
+        #[pin]
+        future: F,
+    }
+
+    impl<T, Res, F> Future for WithHooksFuture<T, Res, F>
+    where
+        Res: Fn(T, ResponseInfo) + Send + 'static,
+        T: Send + 'static,
+        F: TryFuture,
+        F::Ok: Reply,
+        F::Error: IsReject,
+    {
+        type Output = Result<(Hooked,), F::Error>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            let pin = self.project();
+            let result = ready!(pin.future.try_poll(cx));
+            let mut guard = pin.guard.take().expect("polled after complete");
+            match result {
+                Ok(reply) => {
+                    let resp = reply.into_response();
+                    guard.status = Some(resp.status());
+                    Poll::Ready(Ok((Hooked(watch(resp, guard, *pin.head)),)))
+                }
+                Err(reject) => {
+                    guard.status = Some(reject.status());
+                    guard.finish(Disposition::Rejected);
+                    Poll::Ready(Err(reject))
+                }
+            }
+        }
+    }
+
+    // Moves the guard into the response body, so it finishes once the last
+    // of the body has been given to hyper, or is dropped with it.
+    fn watch<T, Res>(resp: Response, mut guard: Guard<T, Res>, head: bool) -> Response
+    where
+        Res: Fn(T, ResponseInfo) + Send + 'static,
+        T: Send + 'static,
+    {
+        // hyper never sends a body for these, so there's nothing to wait for.
+        let status = resp.status();
+        if head || status == StatusCode::NO_CONTENT || status == StatusCode::NOT_MODIFIED {
+            guard.finish(Disposition::Completed);
+            return resp;
+        }
+        if HttpBody::size_hint(resp.body()).exact() == Some(0) {
+            guard.finish(Disposition::Completed);
+            return resp;
+        }
+        // Wrapping the body hides its length from hyper, so it's sent chunked,
+        // unless the reply set a `content-length` itself. Outer wraps may still
+        // change the body, so the length isn't added here.
+        let remaining = resp
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|len| len.to_str().ok())
+            .and_then(|len| len.parse().ok());
+        let (parts, body) = resp.into_parts();
+        let body = HookedBody {
+            body,
+            guard,
+            remaining,
+        };
+        Response::from_parts(parts, Body::wrap_stream(body))
+    }
+
+    #[pin_project]
+    struct HookedBody<T, Res>
+    where
+        Res: Fn(T, ResponseInfo),
+    {
+        #[pin]
+        body: Body,
+        guard: Guard<T, Res>,
+        // hyper stops polling once a body's `content-length` has been sent,
+        // so the end is found by counting instead of waiting for `None`.
+        remaining: Option<u64>,
+    }
+
+    impl<T, Res> Stream for HookedBody<T, Res>
+    where
+        Res: Fn(T, ResponseInfo),
+    {
+        type Item = Result<Bytes, hyper::Error>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            let pin = self.project();
+            let item = ready!(pin.body.poll_next(cx));
+            match item {
+                None => pin.guard.finish(Disposition::Completed),
+                Some(Err(_)) => pin.guard.finish(Disposition::Aborted),
+                Some(Ok(ref chunk)) => {
+                    if let Some(remaining) = pin.remaining {
+                        *remaining = remaining.saturating_sub(chunk.len() as u64);
+                        if *remaining == 0 {
+                            pin.guard.finish(Disposition::Completed);
+                        }
+                    }
+                }
+            }
+            Poll::Ready(item)
+        }
+    }
+}
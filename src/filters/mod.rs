@@ -11,8 +11,10 @@ pub mod compression;
 pub mod cookie;
 pub mod cors;
 pub mod ext;
+pub mod fallback;
 pub mod fs;
 pub mod header;
+pub mod hooks;
 pub mod log;
 pub mod method;
 #[cfg(feature = "multipart")]
@@ -21,7 +23,10 @@ pub mod path;
 pub mod query;
 pub mod reply;
 pub mod sse;
+pub mod upgrade;
 #[cfg(feature = "websocket")]
 pub mod ws;
 
+pub use self::fallback::fallback;
+pub use self::hooks::hooks;
 pub use crate::filter::BoxedFilter;
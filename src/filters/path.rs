@@ -4,6 +4,7 @@
 //!
 //! - [`path`](./fn.path.html) matches a specific segment, like `/foo`.
 //! - [`param`](./fn.param.html) tries to parse a segment into a type, like `/:u16`.
+//! - [`param_optional`](./fn.param_optional.html) parses an optional last segment, like `/:u16?`.
 //! - [`end`](./fn.end.html) matches when the path end is found.
 //! - [`path!`](../../macro.path.html) eases combining multiple `path` and `param` filters.
 //!
@@ -128,6 +129,7 @@
 use std::any::TypeId;
 use std::convert::Infallible;
 use std::fmt;
+use std::marker::PhantomData;
 use std::str::FromStr;
 
 use futures::future;
@@ -282,10 +284,102 @@ pub fn param<T: FromStr + Send + 'static>(
     });
     document::explicit(filter, |path| {
         let index = path.parameters.len();
-        path.parameter(parameter(format!("param{}", index+1), TypeId::of::<T>()).required(true))
+        path.upsert_parameter(
+            parameter(format!("param{}", index + 1), TypeId::of::<T>()).required(true),
+        )
     })
 }
 
+/// Extract an optional parameter from the last path segment.
+///
+/// If there is no path left to match, this extracts `None`. Otherwise the
+/// current segment is parsed like [`param()`](./fn.param.html), extracting
+/// `Some(value)` on success.
+///
+/// If a segment exists but could not be parsed, rejects with a `404 Not Found`,
+/// so that a later `or()` branch may still match the request.
+///
+/// # Note
+///
+/// This filter is meant to be the last path component, and should be followed
+/// by [`end()`](./fn.end.html). Placing other path filters after it would make
+/// them match against different segments depending on whether the optional
+/// one was present.
+///
+/// # Documentation
+///
+/// Path parameters are always required in OpenAPI, so this filter documents
+/// itself as two routes: one without the trailing segment, and one with a
+/// required parameter.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+///
+/// // Matches both '/reports/2020' and '/reports/2020/4'
+/// let route = warp::path("reports")
+///     .and(warp::path::param::<u16>())
+///     .and(warp::path::param_optional::<u8>())
+///     .and(warp::path::end())
+///     .map(|year: u16, month: Option<u8>| {
+///         match month {
+///             Some(month) => format!("Report for {}/{}", month, year),
+///             None => format!("Report for {}", year),
+///         }
+///     });
+/// ```
+pub fn param_optional<T: FromStr + Send + 'static>(
+) -> impl Filter<Extract = One<Option<T>>, Error = Rejection> + Copy {
+    ParamOptional(PhantomData)
+}
+
+#[allow(missing_debug_implementations)]
+struct ParamOptional<T>(PhantomData<fn() -> T>);
+
+impl<T> Clone for ParamOptional<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ParamOptional<T> {}
+
+impl<T> FilterBase for ParamOptional<T>
+where
+    T: FromStr + Send + 'static,
+{
+    type Extract = One<Option<T>>;
+    type Error = Rejection;
+    type Future = future::Ready<Result<Self::Extract, Self::Error>>;
+
+    fn filter(&self, _: Internal) -> Self::Future {
+        route::with(|route| {
+            if route.path().is_empty() {
+                return future::ok(one(None));
+            }
+            future::ready(with_segment(route, |seg| {
+                log::trace!("param_optional?: {:?}", seg);
+                if seg.is_empty() {
+                    return Err(reject::not_found());
+                }
+                T::from_str(seg)
+                    .map(|value| one(Some(value)))
+                    .map_err(|_| reject::not_found())
+            }))
+        })
+    }
+
+    fn describe(&self, route: RouteDocumentation) -> Vec<RouteDocumentation> {
+        let mut with_param = route.clone();
+        let index = with_param.parameters.len();
+        with_param.upsert_parameter(
+            parameter(format!("param{}", index + 1), TypeId::of::<T>()).required(true),
+        );
+        vec![route, with_param]
+    }
+}
+
 /// Extract the unmatched tail of the path.
 ///
 /// This will return a `Tail`, which allows access to the rest of the path
@@ -4,7 +4,8 @@ use futures::future;
 use serde::de::DeserializeOwned;
 use serde_urlencoded;
 
-use crate::filter::{filter_fn_one, Filter, One};
+use crate::document;
+use crate::filter::{filter_fn, filter_fn_one, Filter, One};
 use crate::reject::{self, Rejection};
 
 /// Creates a `Filter` that decodes query parameters to the type `T`.
@@ -37,3 +38,39 @@ pub fn raw() -> impl Filter<Extract = One<String>, Error = Rejection> + Copy {
         future::ready(route)
     })
 }
+
+/// Require the raw query string to be at most `limit` bytes long.
+///
+/// The length is checked before the query string is decoded or copied, so
+/// this should come before other query filters. If the query string is
+/// longer, the request is rejected with a `414 URI Too Long`.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+///
+/// // Limit the query string to 1kb...
+/// let route = warp::query::max_length(1024)
+///     .and(warp::query::raw())
+///     .map(|query: String| {
+///         format!("query: {}", query)
+///     });
+/// ```
+pub fn max_length(limit: usize) -> impl Filter<Extract = (), Error = Rejection> + Copy {
+    let filter = filter_fn(move |route| {
+        let length = route.query().map_or(0, str::len);
+        if length <= limit {
+            future::ok(())
+        } else {
+            log::debug!("query length: {} is over limit {}", length, limit);
+            future::err(reject::uri_too_long())
+        }
+    });
+    document::explicit(filter, move |route| {
+        route.upsert_response(
+            document::response(414, None)
+                .description(format!("The query string is longer than {} bytes.", limit)),
+        )
+    })
+}
@@ -0,0 +1,165 @@
+//! HTTP Upgrade Filters
+//!
+//! These filters allow switching a connection to some other protocol, using
+//! the HTTP/1.1 `Upgrade` mechanism. The [`ws`](../ws/index.html) filters are
+//! built for the WebSocket protocol specifically, while these hand over the
+//! raw connection.
+
+use std::fmt;
+use std::future::Future;
+
+use futures::{future, FutureExt, TryFutureExt};
+use headers::{Connection, HeaderMapExt};
+use http::header::{HeaderValue, UPGRADE};
+
+use super::{body, header};
+use crate::filter::{Filter, One};
+use crate::reject::Rejection;
+use crate::reply::{Reply, Response};
+
+pub use hyper::upgrade::Upgraded;
+
+/// Creates a Filter that matches requests asking to upgrade to `protocol`.
+///
+/// The yielded `Upgrade` is used to finish the upgrade.
+///
+/// # Note
+///
+/// This filter combines multiple filters internally, so you don't need them:
+///
+/// - Header `connection` must include `upgrade`
+/// - Header `upgrade` must be `protocol` (case insensitive)
+///
+/// Any method is allowed. Requests that don't ask for the upgrade are
+/// rejected, so other routes may still handle the same path.
+///
+/// If the filters are met, yields an `Upgrade`. Calling `Upgrade::on_upgrade`
+/// will return a reply with:
+///
+/// - Status of `101 Switching Protocols`
+/// - Header `connection: upgrade`
+/// - Header `upgrade` set to `protocol`
+///
+/// # Panics
+///
+/// `protocol` must be a valid header value.
+///
+/// # Example
+///
+/// ```
+/// use warp::Filter;
+/// use warp::filters::upgrade::Upgraded;
+///
+/// let echo = warp::filters::upgrade::on("echo")
+///     .map(|upgrade: warp::filters::upgrade::Upgrade| {
+///         upgrade.on_upgrade(|upgraded: Upgraded| async move {
+///             let (mut rd, mut wr) = tokio::io::split(upgraded);
+///             let _ = tokio::io::copy(&mut rd, &mut wr).await;
+///         })
+///     });
+/// ```
+pub fn on(protocol: &'static str) -> impl Filter<Extract = One<Upgrade>, Error = Rejection> + Copy {
+    // Checked here, so building the 101 response can't fail later.
+    HeaderValue::from_static(protocol);
+
+    connection_has_upgrade()
+        .and(header::exact_ignore_case("upgrade", protocol))
+        .and(body::body())
+        .map(move |body: ::hyper::Body| Upgrade { body, protocol })
+}
+
+pub(crate) fn connection_has_upgrade() -> impl Filter<Extract = (), Error = Rejection> + Copy {
+    header::header2()
+        .and_then(|conn: ::headers::Connection| {
+            if conn.contains("upgrade") {
+                future::ok(())
+            } else {
+                future::err(crate::reject::known(MissingConnectionUpgrade))
+            }
+        })
+        .untuple_one()
+}
+
+/// Extracted by the [`on`](on) filter, and used to finish an upgrade.
+pub struct Upgrade {
+    body: ::hyper::Body,
+    protocol: &'static str,
+}
+
+impl Upgrade {
+    /// Finish the upgrade, passing a function to handle the `Upgraded` connection.
+    ///
+    /// The passed function must return a `Future`.
+    pub fn on_upgrade<F, U>(self, func: F) -> impl Reply
+    where
+        F: FnOnce(Upgraded) -> U + Send + 'static,
+        U: Future<Output = ()> + Send + 'static,
+    {
+        UpgradeReply {
+            upgrade: self,
+            on_upgrade: func,
+        }
+    }
+}
+
+impl fmt::Debug for Upgrade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Upgrade")
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
+
+#[allow(missing_debug_implementations)]
+struct UpgradeReply<F> {
+    upgrade: Upgrade,
+    on_upgrade: F,
+}
+
+impl<F, U> Reply for UpgradeReply<F>
+where
+    F: FnOnce(Upgraded) -> U + Send + 'static,
+    U: Future<Output = ()> + Send + 'static,
+{
+    fn into_response(self) -> Response {
+        let on_upgrade = self.on_upgrade;
+        let protocol = self.upgrade.protocol;
+        let fut = self
+            .upgrade
+            .body
+            .on_upgrade()
+            .and_then(move |upgraded| {
+                log::trace!("{} upgrade complete", protocol);
+                on_upgrade(upgraded).map(Ok)
+            })
+            .map(move |result| {
+                if let Err(err) = result {
+                    log::debug!("{} upgrade error: {}", protocol, err);
+                }
+            });
+        ::tokio::task::spawn(fut);
+
+        let mut res = http::Response::default();
+
+        *res.status_mut() = http::StatusCode::SWITCHING_PROTOCOLS;
+
+        res.headers_mut().typed_insert(Connection::upgrade());
+        res.headers_mut()
+            .insert(UPGRADE, HeaderValue::from_static(protocol));
+
+        res
+    }
+}
+
+// ===== Rejections =====
+
+#[derive(Debug)]
+pub(crate) struct MissingConnectionUpgrade;
+
+impl ::std::fmt::Display for MissingConnectionUpgrade {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Connection header did not include 'upgrade'")
+    }
+}
+
+impl ::std::error::Error for MissingConnectionUpgrade {}
@@ -6,7 +6,7 @@ use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use super::{body, header};
+use super::{body, header, upgrade};
 use crate::filter::{Filter, One};
 use crate::reject::Rejection;
 use crate::reply::{Reply, Response};
@@ -40,18 +40,8 @@ use tokio_tungstenite::{
 /// - Header `upgrade: websocket`
 /// - Header `sec-websocket-accept` with the hash value of the received key.
 pub fn ws() -> impl Filter<Extract = One<Ws>, Error = Rejection> + Copy {
-    let connection_has_upgrade = header::header2()
-        .and_then(|conn: ::headers::Connection| {
-            if conn.contains("upgrade") {
-                future::ok(())
-            } else {
-                future::err(crate::reject::known(MissingConnectionUpgrade))
-            }
-        })
-        .untuple_one();
-
     crate::get()
-        .and(connection_has_upgrade)
+        .and(upgrade::connection_has_upgrade())
         .and(header::exact_ignore_case("upgrade", "websocket"))
         .and(header::exact("sec-websocket-version", "13"))
         //.and(header::exact2(Upgrade::websocket()))
@@ -358,16 +348,3 @@ impl Into<Vec<u8>> for Message {
         self.into_bytes()
     }
 }
-
-// ===== Rejections =====
-
-#[derive(Debug)]
-pub(crate) struct MissingConnectionUpgrade;
-
-impl ::std::fmt::Display for MissingConnectionUpgrade {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-        write!(f, "Connection header did not include 'upgrade'")
-    }
-}
-
-impl ::std::error::Error for MissingConnectionUpgrade {}
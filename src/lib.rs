@@ -144,6 +144,7 @@ pub use self::filters::{
     // query() function
     query::query,
     sse,
+    upgrade,
 };
 // ws() function
 #[cfg(feature = "websocket")]
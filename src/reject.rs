@@ -97,6 +97,12 @@ pub(crate) fn payload_too_large() -> Rejection {
     known(PayloadTooLarge { _p: () })
 }
 
+// 414 URI Too Long
+#[inline]
+pub(crate) fn uri_too_long() -> Rejection {
+    known(UriTooLong { _p: () })
+}
+
 // 415 Unsupported Media Type
 //
 // Used by the body filters if the request payload content-type doesn't match
@@ -248,16 +254,17 @@ enum_known! {
     InvalidQuery(InvalidQuery),
     LengthRequired(LengthRequired),
     PayloadTooLarge(PayloadTooLarge),
+    UriTooLong(UriTooLong),
     UnsupportedMediaType(UnsupportedMediaType),
     FileOpenError(crate::fs::FileOpenError),
     FilePermissionError(crate::fs::FilePermissionError),
     BodyReadError(crate::body::BodyReadError),
     BodyDeserializeError(crate::body::BodyDeserializeError),
     CorsForbidden(crate::cors::CorsForbidden),
-    #[cfg(feature = "websocket")]
-    MissingConnectionUpgrade(crate::ws::MissingConnectionUpgrade),
+    MissingConnectionUpgrade(crate::filters::upgrade::MissingConnectionUpgrade),
     MissingExtension(crate::ext::MissingExtension),
     BodyConsumedMultipleTimes(crate::body::BodyConsumedMultipleTimes),
+    FallbackNotFound(crate::filters::fallback::FallbackNotFound),
 }
 
 impl Rejection {
@@ -391,10 +398,11 @@ impl Rejections {
                 | Known::InvalidQuery(_)
                 | Known::BodyReadError(_)
                 | Known::BodyDeserializeError(_) => StatusCode::BAD_REQUEST,
-                #[cfg(feature = "websocket")]
                 Known::MissingConnectionUpgrade(_) => StatusCode::BAD_REQUEST,
+                Known::FallbackNotFound(_) => StatusCode::NOT_FOUND,
                 Known::LengthRequired(_) => StatusCode::LENGTH_REQUIRED,
                 Known::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+                Known::UriTooLong(_) => StatusCode::URI_TOO_LONG,
                 Known::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
                 Known::FilePermissionError(_) | Known::CorsForbidden(_) => StatusCode::FORBIDDEN,
                 Known::FileOpenError(_)
@@ -408,6 +416,7 @@ impl Rejections {
 
     fn into_response(&self) -> crate::reply::Response {
         match *self {
+            Rejections::Known(Known::FallbackNotFound(ref fallback)) => fallback.response(),
             Rejections::Known(ref e) => {
                 let mut res = http::Response::new(Body::from(e.to_string()));
                 *res.status_mut() = self.status();
@@ -495,6 +504,11 @@ unit_error! {
     pub PayloadTooLarge: "The request payload is too large"
 }
 
+unit_error! {
+    /// The request URI is too long
+    pub UriTooLong: "The request URI is too long"
+}
+
 unit_error! {
     /// The request's content-type is not supported
     pub UnsupportedMediaType: "The request's content-type is not supported"
@@ -684,6 +698,7 @@ mod tests {
         );
         assert_eq!(length_required().status(), StatusCode::LENGTH_REQUIRED);
         assert_eq!(payload_too_large().status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(uri_too_long().status(), StatusCode::URI_TOO_LONG);
         assert_eq!(
             unsupported_media_type().status(),
             StatusCode::UNSUPPORTED_MEDIA_TYPE
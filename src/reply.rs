@@ -73,6 +73,29 @@ pub fn reply() -> impl Reply {
     StatusCode::OK
 }
 
+/// Converts any `Reply` into a `Response`, without serving it.
+///
+/// This is the supported way to get at the response a `Reply` would send,
+/// such as in unit tests of functions building replies, or to hand a reply
+/// to some other `hyper` service.
+///
+/// # Example
+///
+/// ```
+/// use warp::http::StatusCode;
+///
+/// fn created() -> impl warp::Reply {
+///     warp::reply::with_status("created", StatusCode::CREATED)
+/// }
+///
+/// let resp = warp::reply::into_response(created());
+/// assert_eq!(resp.status(), 201);
+/// ```
+#[inline]
+pub fn into_response(reply: impl Reply) -> Response {
+    reply.into_response()
+}
+
 /// Convert the value into a `Reply` with the value encoded as JSON.
 ///
 /// The passed value must implement [`Serialize`][ser]. Many
@@ -572,6 +595,13 @@ mod tests {
         assert_eq!(res.status(), 500);
     }
 
+    #[test]
+    fn into_response_with_status() {
+        let res = super::into_response(with_status(json(&"created"), StatusCode::CREATED));
+        assert_eq!(res.status(), 201);
+        assert_eq!(res.headers()["content-type"], "application/json");
+    }
+
     #[test]
     fn boxed_reply() {
         let r: Box<dyn Reply> = Box::new(reply());
@@ -26,14 +26,19 @@ where
     F::Error: IsReject,
 {
     Server {
+        max_uri_length: DEFAULT_MAX_URI_LENGTH,
         pipeline: false,
         filter,
     }
 }
 
+// 16kb, twice what most proxies allow for the whole request line.
+const DEFAULT_MAX_URI_LENGTH: usize = 16 * 1024;
+
 /// A Warp Server ready to filter requests.
 #[derive(Debug)]
 pub struct Server<F> {
+    max_uri_length: usize,
     pipeline: bool,
     filter: F,
 }
@@ -50,18 +55,32 @@ pub struct TlsServer<F> {
 // Getting all various generic bounds to make this a re-usable method is
 // very complicated, so instead this is just a macro.
 macro_rules! into_service {
-    ($into:expr) => {{
+    ($into:expr, $max_uri_length:expr) => {{
         let inner = crate::service($into);
+        let max_uri_length = $max_uri_length;
         make_service_fn(move |transport| {
             let inner = inner.clone();
             let remote_addr = Transport::remote_addr(transport);
-            future::ok::<_, Infallible>(service_fn(move |req| {
-                inner.call_with_addr(req, remote_addr)
+            future::ok::<_, Infallible>(service_fn(move |req: crate::Request| {
+                let length = uri_length(req.uri());
+                if length > max_uri_length {
+                    log::debug!("uri length: {} is over limit {}", length, max_uri_length);
+                    let res = crate::reject::uri_too_long().into_response();
+                    return future::Either::Left(future::ok(res));
+                }
+                future::Either::Right(inner.call_with_addr(req, remote_addr))
             }))
         })
     }};
 }
 
+// The length of the request target, without needing to format the whole `Uri`.
+fn uri_length(uri: &http::Uri) -> usize {
+    uri.authority()
+        .map_or(0, |authority| authority.as_str().len())
+        + uri.path_and_query().map_or(0, |path| path.as_str().len())
+}
+
 macro_rules! addr_incoming {
     ($addr:expr) => {{
         let mut incoming = AddrIncoming::bind($addr)?;
@@ -73,7 +92,7 @@ macro_rules! addr_incoming {
 
 macro_rules! bind_inner {
     ($this:ident, $addr:expr) => {{
-        let service = into_service!($this.filter);
+        let service = into_service!($this.filter, $this.max_uri_length);
         let (addr, incoming) = addr_incoming!($addr);
         let srv = HyperServer::builder(incoming)
             .http1_pipeline_flush($this.pipeline)
@@ -82,7 +101,7 @@ macro_rules! bind_inner {
     }};
 
     (tls: $this:ident, $addr:expr) => {{
-        let service = into_service!($this.server.filter);
+        let service = into_service!($this.server.filter, $this.server.max_uri_length);
         let (addr, incoming) = addr_incoming!($addr);
         let tls = $this.tls.build()?;
         let srv = HyperServer::builder(crate::tls::TlsAcceptor::new(tls, incoming))
@@ -308,7 +327,7 @@ where
         I::Ok: Transport + Send + 'static + Unpin,
         I::Error: Into<Box<dyn StdError + Send + Sync>>,
     {
-        let service = into_service!(self.filter);
+        let service = into_service!(self.filter, self.max_uri_length);
 
         let srv = HyperServer::builder(hyper::server::accept::from_stream(incoming.into_stream()))
             .http1_pipeline_flush(self.pipeline)
@@ -320,6 +339,29 @@ where
         }
     }
 
+    /// Set the maximum length of a request's URI, in bytes.
+    ///
+    /// Requests with a longer URI are replied to with `414 URI Too Long`,
+    /// before any filter is run. Defaults to 16kb.
+    pub fn max_uri_length(mut self, limit: usize) -> Self {
+        self.max_uri_length = limit;
+        self
+    }
+
+    /// Log a table of every route this server can match, like
+    /// [`print_routes`](crate::document::print_routes).
+    ///
+    /// The table is logged once, at the `info` level, and is only built if
+    /// that level is enabled.
+    pub fn log_routes(self) -> Self {
+        if log::log_enabled!(log::Level::Info) {
+            let routes = crate::document::summarize(&self.filter);
+            let table = crate::document::routes_table(&routes, false);
+            log::info!("routes:\n{}", table.trim_end());
+        }
+        self
+    }
+
     // Generally shouldn't be used, as it can slow down non-pipelined responses.
     //
     // It's only real use is to make silly pipeline benchmarks look better.
@@ -79,6 +79,22 @@
 //!     assert_eq!(res.body(), "Sum is 3");
 //! }
 //! ```
+//!
+//! # Testing Replies
+//!
+//! Functions that build a `Reply` can be tested without any filter, by
+//! converting the reply with [`reply::into_response`](crate::reply::into_response):
+//!
+//! ```
+//! use warp::http::StatusCode;
+//!
+//! fn created() -> impl warp::Reply {
+//!     warp::reply::with_status("created", StatusCode::CREATED)
+//! }
+//!
+//! let res = warp::reply::into_response(created());
+//! assert_eq!(res.status(), 201);
+//! ```
 use std::convert::TryFrom;
 use std::error::Error as StdError;
 use std::fmt;
@@ -370,7 +386,7 @@ impl RequestBuilder {
         let mut fut = Box::pin(
             route::set(&route, move || f.filter(crate::filter::Internal)).then(|result| {
                 let res = match result {
-                    Ok(rep) => rep.into_response(),
+                    Ok(rep) => crate::reply::into_response(rep),
                     Err(rej) => {
                         log::debug!("rejected: {:?}", rej);
                         rej.into_response()
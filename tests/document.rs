@@ -0,0 +1,547 @@
+#![deny(warnings)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use warp::document::{self, describe};
+use warp::Filter;
+
+fn todo_routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let list = warp::get()
+        .and(warp::path("todos"))
+        .and(warp::path::end())
+        .and(document::document(document::description("List todos")))
+        .and(document::document(document::tag("todos")))
+        .map(warp::reply);
+    let get = warp::get()
+        .and(warp::path("todos"))
+        .and(document::param::<u64>("id", "The todo's id"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<String>("x-request-id"))
+        .and(document::document(document::response(404, None)))
+        .map(|_, _| warp::reply());
+    let create = warp::post()
+        .and(warp::path("todos"))
+        .and(warp::path::end())
+        .and(warp::body::content_length_limit(1024))
+        .and(warp::cookie("session"))
+        .and(document::document(
+            document::body(document::string()).mime("text/plain"),
+        ))
+        .map(|_| warp::reply());
+    let update = document::explicit(
+        warp::put()
+            .and(warp::path("todos"))
+            .and(warp::path::param::<u64>())
+            .and(warp::path::end())
+            .map(|_| warp::reply()),
+        |route: &mut document::RouteDocumentation| {
+            route.method = warp::http::Method::PUT;
+            route.push_path("todos");
+            route.parameter(
+                document::parameter("id", document::integer()).description("The todo's id"),
+            );
+            route.description("Update a todo");
+            route.header(
+                document::header("if-match")
+                    .description("The todo's etag")
+                    .required(false),
+            );
+            route.cookie(document::cookie("session").description("The login session"));
+            route.body(document::body(document::string()).mime("text/plain"));
+            route.response(
+                document::response(
+                    200,
+                    document::body(document::boolean()).mime("application/json"),
+                )
+                .description("Whether the todo changed")
+                .header(document::header("etag").description("The todo's new etag")),
+            );
+        },
+    );
+    list.or(get).or(create).or(update)
+}
+
+#[test]
+fn describe_is_repeatable() {
+    let routes = todo_routes();
+
+    let first = describe(&routes);
+    let second = describe(&routes);
+
+    assert_eq!(first.len(), 4);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn describe_runs_explicit_each_time() {
+    let calls = AtomicUsize::new(0);
+    let route = document::explicit(warp::any().map(warp::reply), |route| {
+        let call = calls.fetch_add(1, Ordering::SeqCst);
+        route.header(document::header("x-call").description(format!("Call {}", call)));
+    });
+
+    let first = describe(&route);
+    let second = describe(&route);
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn duplicate_header_merges() {
+    let route = warp::header::<String>("x-request-id")
+        .and(document::document(
+            |route: &mut document::RouteDocumentation| {
+                route.header(
+                    document::header("x-request-id").description("Used to trace the request"),
+                )
+            },
+        ))
+        .and(warp::header::optional::<String>("x-request-id"))
+        .map(|_, _| warp::reply());
+
+    let routes = describe(&route);
+    let headers = &routes[0].headers;
+
+    assert_eq!(headers.len(), 1);
+    let header = headers.iter().next().unwrap();
+    assert_eq!(
+        header.description.as_deref(),
+        Some("Used to trace the request")
+    );
+    assert!(header.required);
+}
+
+#[test]
+fn explicit_description_before_filter_wins() {
+    let route = document::document(|route: &mut document::RouteDocumentation| {
+        route.header(document::header("content-length").description("My explicit description"));
+        route.response(document::response(413, None).description("Too big"));
+    })
+    .and(warp::body::content_length_limit(1024))
+    .map(warp::reply);
+
+    let routes = describe(&route);
+
+    let header = routes[0].headers.iter().next().unwrap();
+    assert_eq!(routes[0].headers.len(), 1);
+    assert_eq!(
+        header.description.as_deref(),
+        Some("My explicit description")
+    );
+    assert!(header.required);
+    let response = routes[0].responses.iter().next().unwrap();
+    assert_eq!(routes[0].responses.len(), 1);
+    assert_eq!(response.description, "Too big");
+}
+
+#[test]
+fn duplicate_response_header_merges() {
+    let mut route = document::RouteDocumentation::default();
+    route.response(
+        document::response(200, None)
+            .description("OK")
+            .header(document::header("x-request-id").description("Used to trace the request")),
+    );
+    route.upsert_response(document::response(200, None).header(document::header("x-request-id")));
+
+    assert_eq!(route.responses.len(), 1);
+    let response = route.responses.iter().next().unwrap();
+    assert_eq!(response.description, "OK");
+    assert_eq!(response.headers.len(), 1);
+    let header = response.headers.iter().next().unwrap();
+    assert_eq!(
+        header.description.as_deref(),
+        Some("Used to trace the request")
+    );
+}
+
+fn route_table_routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone
+{
+    let list = warp::get()
+        .and(warp::path("todos"))
+        .and(warp::path::end())
+        .and(document::document(document::description(
+            "List todos\nWith more detail",
+        )))
+        .and(document::document(document::tag("todos")))
+        .and(document::document(document::tag("public")))
+        .map(warp::reply);
+    let nested = warp::delete()
+        .and(warp::path!(
+            "users" / "settings" / "notifications" / "email"
+        ))
+        .and(document::param::<u64>(
+            "subscription_id",
+            "The subscription",
+        ))
+        .and(warp::path::end())
+        .map(|_| warp::reply());
+    list.or(nested)
+}
+
+#[test]
+fn route_table_aligns_columns() {
+    let routes = document::summarize(&route_table_routes());
+    let table = document::routes_table(&routes, false);
+    let lines = table.lines().collect::<Vec<_>>();
+
+    assert_eq!(
+        lines,
+        vec![
+            "METHOD  PATH                                                   SUMMARY     TAGS",
+            "GET     /todos                                                 List todos  todos, public",
+            "DELETE  /users/settings/notifications/email/{subscription_id}",
+        ]
+    );
+}
+
+#[test]
+fn route_table_colors_methods() {
+    let routes = document::summarize(&route_table_routes());
+    let plain = document::routes_table(&routes, false);
+    let colored = document::routes_table(&routes, true);
+
+    assert!(colored.contains("\x1b[32mGET\x1b[0m     /todos"));
+    assert!(colored.contains("\x1b[31mDELETE\x1b[0m  /users"));
+    assert_eq!(
+        colored
+            .replace("\x1b[32m", "")
+            .replace("\x1b[31m", "")
+            .replace("\x1b[0m", ""),
+        plain
+    );
+}
+
+#[test]
+fn route_summaries_match_describe_order() {
+    let routes = route_table_routes();
+    let described = describe(&routes);
+    let summaries = document::summarize(&routes);
+
+    let paths = described
+        .iter()
+        .map(|route| route.pretty_path())
+        .collect::<Vec<_>>();
+    let summary_paths = summaries
+        .iter()
+        .map(|route| route.path.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(summary_paths, paths);
+}
+
+#[test]
+fn routes_json() {
+    let json = document::routes_json(&route_table_routes());
+
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {
+                "method": "GET",
+                "path": "/todos",
+                "summary": "List todos",
+                "tags": ["todos", "public"],
+            },
+            {
+                "method": "DELETE",
+                "path": "/users/settings/notifications/email/{subscription_id}",
+                "summary": null,
+                "tags": [],
+            },
+        ])
+    );
+}
+
+mod merge {
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+    use std::hash::Hash;
+    use warp::document::{self, DocumentedType, RouteDocumentation};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    enum Kind {
+        Header,
+        Cookie,
+        Query,
+        Parameter,
+    }
+
+    // `explicit` picks between the methods for explicit documentation, and
+    // the `upsert_*` methods used by filters for their defaults.
+    #[derive(Clone, Debug)]
+    struct Entry {
+        name: &'static str,
+        description: Option<&'static str>,
+        required: bool,
+        integer: bool,
+        explicit: bool,
+    }
+
+    #[derive(Clone, Debug)]
+    enum Op {
+        Entry(Kind, Entry),
+        // The response is added explicitly if its header is.
+        Response(u16, Option<&'static str>, Entry),
+        Body(Option<&'static str>, bool, bool),
+    }
+
+    fn type_(integer: bool) -> DocumentedType {
+        if integer {
+            document::integer()
+        } else {
+            document::string()
+        }
+    }
+
+    fn entry() -> impl Strategy<Value = Entry> {
+        (
+            prop::sample::select(vec!["a", "b", "x-request-id"]),
+            prop::option::of(prop::sample::select(vec!["first", "second"])),
+            any::<bool>(),
+            any::<bool>(),
+            any::<bool>(),
+        )
+            .prop_map(|(name, description, required, integer, explicit)| Entry {
+                name,
+                description,
+                required,
+                integer,
+                explicit,
+            })
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        let kind = prop::sample::select(vec![
+            Kind::Header,
+            Kind::Cookie,
+            Kind::Query,
+            Kind::Parameter,
+        ]);
+        prop_oneof![
+            (kind, entry()).prop_map(|(kind, entry)| Op::Entry(kind, entry)),
+            (
+                prop::sample::select(vec![200u16, 404]),
+                prop::option::of(prop::sample::select(vec!["OK", "Not Found"])),
+                entry(),
+            )
+                .prop_map(|(status, description, header)| Op::Response(
+                    status,
+                    description,
+                    header
+                )),
+            (
+                prop::option::of(prop::sample::select(vec!["text/plain", "application/json"])),
+                any::<bool>(),
+                any::<bool>(),
+            )
+                .prop_map(|(mime, integer, explicit)| Op::Body(mime, integer, explicit)),
+        ]
+    }
+
+    fn apply(route: &mut RouteDocumentation, op: &Op) {
+        macro_rules! build {
+            ($entry:expr, $item:expr) => {{
+                let item = $item.required($entry.required);
+                match $entry.description {
+                    Some(description) => item.description(description),
+                    None => item,
+                }
+            }};
+        }
+        match op {
+            Op::Entry(Kind::Header, e) => {
+                let header = build!(e, document::header(e.name));
+                if e.explicit {
+                    route.header(header)
+                } else {
+                    route.upsert_header(header)
+                }
+            }
+            Op::Entry(Kind::Cookie, e) => {
+                let cookie = build!(e, document::cookie(e.name));
+                if e.explicit {
+                    route.cookie(cookie)
+                } else {
+                    route.upsert_cookie(cookie)
+                }
+            }
+            Op::Entry(Kind::Query, e) => {
+                let query = build!(e, document::query(e.name, type_(e.integer)));
+                if e.explicit {
+                    route.query(query)
+                } else {
+                    route.upsert_query(query)
+                }
+            }
+            Op::Entry(Kind::Parameter, e) => {
+                let parameter = build!(e, document::parameter(e.name, type_(e.integer)));
+                if e.explicit {
+                    route.parameter(parameter)
+                } else {
+                    route.upsert_parameter(parameter)
+                }
+            }
+            Op::Response(status, description, header) => {
+                let response = document::response(*status, None)
+                    .header(build!(header, document::header(header.name)));
+                let response = match description {
+                    Some(description) => response.description(*description),
+                    None => response,
+                };
+                if header.explicit {
+                    route.response(response)
+                } else {
+                    route.upsert_response(response)
+                }
+            }
+            Op::Body(mime, integer, explicit) => {
+                let body = document::body(type_(*integer));
+                let body = match mime {
+                    Some(mime) => body.mime(*mime),
+                    None => body,
+                };
+                if *explicit {
+                    route.body(body)
+                } else {
+                    route.upsert_body(body)
+                }
+            }
+        }
+    }
+
+    // What an entry should hold after every operation so far: the latest
+    // explicitly documented description and type, falling back to the first
+    // default, and required if it ever was.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Expected {
+        description: Option<String>,
+        required: bool,
+        type_: DocumentedType,
+    }
+
+    fn expect<K: Eq + Hash>(
+        entries: &mut HashMap<K, Expected>,
+        key: K,
+        entry: &Entry,
+        explicit: bool,
+    ) {
+        let description = entry.description.map(str::to_owned);
+        let expected = entries.entry(key).or_insert_with(|| Expected {
+            description: description.clone(),
+            required: entry.required,
+            type_: type_(entry.integer),
+        });
+        if explicit {
+            expected.description = description.or_else(|| expected.description.take());
+            expected.type_ = type_(entry.integer);
+        } else if expected.description.is_none() {
+            expected.description = description;
+        }
+        expected.required |= entry.required;
+    }
+
+    #[derive(Default)]
+    struct Model {
+        entries: HashMap<(Kind, String), Expected>,
+        responses: HashMap<u16, String>,
+        response_headers: HashMap<(u16, String), Expected>,
+        bodies: HashMap<Option<String>, DocumentedType>,
+    }
+
+    impl Model {
+        fn apply(&mut self, op: &Op) {
+            match op {
+                Op::Entry(kind, e) => {
+                    let key = (*kind, e.name.to_owned());
+                    expect(&mut self.entries, key, e, e.explicit)
+                }
+                Op::Response(status, description, header) => {
+                    let existing = self.responses.entry(*status).or_default();
+                    if let Some(description) = description {
+                        if header.explicit || existing.is_empty() {
+                            *existing = description.to_string();
+                        }
+                    }
+                    let key = (*status, header.name.to_owned());
+                    expect(&mut self.response_headers, key, header, header.explicit);
+                }
+                Op::Body(mime, integer, explicit) => {
+                    let mime = mime.map(str::to_owned);
+                    if *explicit || !self.bodies.contains_key(&mime) {
+                        self.bodies.insert(mime, type_(*integer));
+                    }
+                }
+            }
+        }
+
+        fn entry(
+            &self,
+            kind: Kind,
+            name: &str,
+            description: &Option<String>,
+            required: bool,
+        ) -> Option<&Expected> {
+            self.entries
+                .get(&(kind, name.to_owned()))
+                .filter(|expected| {
+                    expected.description == *description && expected.required == required
+                })
+        }
+
+        fn count(&self, kind: Kind) -> usize {
+            self.entries.keys().filter(|(k, _)| *k == kind).count()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn merges_keep_content(ops in prop::collection::vec(op(), 0..32)) {
+            let mut route = RouteDocumentation::default();
+            let mut model = Model::default();
+            for op in &ops {
+                apply(&mut route, op);
+                model.apply(op);
+            }
+
+            prop_assert_eq!(route.headers.len(), model.count(Kind::Header));
+            for h in &route.headers {
+                prop_assert!(model.entry(Kind::Header, &h.name, &h.description, h.required).is_some(), "{:?}", h);
+            }
+            prop_assert_eq!(route.cookies.len(), model.count(Kind::Cookie));
+            for c in &route.cookies {
+                prop_assert!(model.entry(Kind::Cookie, &c.name, &c.description, c.required).is_some(), "{:?}", c);
+            }
+            // Queries and parameters are lists, so this also checks there are no duplicates.
+            prop_assert_eq!(route.queries.len(), model.count(Kind::Query));
+            for q in &route.queries {
+                let expected = model.entry(Kind::Query, &q.name, &q.description, q.required);
+                prop_assert_eq!(expected.map(|e| &e.type_), Some(&q.type_), "{:?}", q);
+            }
+            prop_assert_eq!(route.parameters.len(), model.count(Kind::Parameter));
+            for p in &route.parameters {
+                let expected = model.entry(Kind::Parameter, &p.name, &p.description, p.required);
+                prop_assert_eq!(expected.map(|e| &e.type_), Some(&p.type_), "{:?}", p);
+            }
+
+            prop_assert_eq!(route.responses.len(), model.responses.len());
+            for response in &route.responses {
+                prop_assert_eq!(Some(&response.description), model.responses.get(&response.status));
+                let headers = model.response_headers.keys().filter(|(s, _)| *s == response.status).count();
+                prop_assert_eq!(response.headers.len(), headers);
+                for h in &response.headers {
+                    let expected = model
+                        .response_headers
+                        .get(&(response.status, h.name.clone()))
+                        .map(|expected| (&expected.description, expected.required));
+                    prop_assert_eq!(expected, Some((&h.description, h.required)), "{:?}", h);
+                }
+            }
+
+            prop_assert_eq!(route.bodies.len(), model.bodies.len());
+            for body in &route.bodies {
+                prop_assert_eq!(model.bodies.get(&body.mime), Some(&body.body), "{:?}", body);
+            }
+        }
+    }
+}
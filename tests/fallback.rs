@@ -0,0 +1,185 @@
+#![deny(warnings)]
+
+use warp::document::describe;
+use warp::Filter;
+
+fn not_found_page() -> impl warp::Reply {
+    warp::reply::html("<h1>Nothing to see here</h1>")
+}
+
+#[tokio::test]
+async fn replies_not_found() {
+    let _ = pretty_env_logger::try_init();
+
+    let hello = warp::get()
+        .and(warp::path("hello"))
+        .and(warp::path::end())
+        .map(|| "Hello, World!");
+    let routes = hello.or(warp::filters::fallback(not_found_page));
+
+    let res = warp::test::request().path("/hello").reply(&routes).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.body(), "Hello, World!");
+
+    let res = warp::test::request()
+        .path("/nope/nope")
+        .reply(&routes)
+        .await;
+    assert_eq!(res.status(), 404);
+    assert_eq!(res.headers()["content-type"], "text/html; charset=utf-8");
+    assert_eq!(res.body(), "<h1>Nothing to see here</h1>");
+}
+
+#[tokio::test]
+async fn status_is_always_not_found() {
+    let _ = pretty_env_logger::try_init();
+
+    let routes = warp::path("hello")
+        .map(warp::reply)
+        .or(warp::filters::fallback(|| "oops"));
+
+    let res = warp::test::request().path("/nope").reply(&routes).await;
+    assert_eq!(res.status(), 404);
+    assert_eq!(res.body(), "oops");
+}
+
+#[tokio::test]
+async fn method_not_allowed_wins() {
+    let _ = pretty_env_logger::try_init();
+
+    // The method is checked after the path, so only `/hello` is a 405.
+    let hello = warp::path("hello")
+        .and(warp::path::end())
+        .and(warp::get())
+        .map(|| "Hello, World!");
+    let routes = hello.or(warp::filters::fallback(not_found_page));
+
+    let res = warp::test::request()
+        .method("POST")
+        .path("/hello")
+        .reply(&routes)
+        .await;
+    assert_eq!(res.status(), 405);
+
+    // A 404 from a path mismatch still uses the fallback.
+    let res = warp::test::request()
+        .method("POST")
+        .path("/nope")
+        .reply(&routes)
+        .await;
+    assert_eq!(res.status(), 404);
+    assert_eq!(res.body(), "<h1>Nothing to see here</h1>");
+}
+
+#[test]
+fn hidden_by_default() {
+    let hello = warp::get()
+        .and(warp::path("hello"))
+        .and(warp::path::end())
+        .map(|| "Hello, World!");
+
+    let without = describe(&hello);
+    let with = describe(&hello.or(warp::filters::fallback(not_found_page)));
+
+    assert_eq!(with, without);
+}
+
+#[test]
+fn documents_wildcard() {
+    let hello = warp::get()
+        .and(warp::path("hello"))
+        .and(warp::path::end())
+        .map(|| "Hello, World!");
+
+    let without = describe(&hello);
+    let with = describe(&hello.or(warp::filters::fallback(not_found_page).documented()));
+
+    assert_eq!(with.len(), 2);
+    assert_eq!(with[0], without[0]);
+
+    let fallback = &with[1];
+    assert_eq!(fallback.pretty_path(), "/{fallback_path}");
+    assert_eq!(fallback.parameters.len(), 1);
+    assert!(fallback.description.as_ref().unwrap().contains("wildcard"));
+    assert_eq!(fallback.responses.len(), 1);
+    assert!(fallback.responses.iter().all(|res| res.status == 404));
+}
+
+#[test]
+fn documents_without_merging_parameters() {
+    let route = warp::path("files")
+        .and(warp::document::param::<String>("path", "A file's path"))
+        .and(warp::filters::fallback(not_found_page).documented());
+
+    let routes = describe(&route);
+
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].pretty_path(), "/files/{path}/{fallback_path}");
+    assert_eq!(routes[0].parameters.len(), 2);
+    assert_eq!(
+        routes[0].parameters[0].description.as_deref(),
+        Some("A file's path")
+    );
+}
+
+#[cfg(feature = "openapi")]
+#[test]
+fn openapi_keeps_index_route() {
+    use openapiv3::{ReferenceOr, StatusCode};
+
+    let index = warp::path::end()
+        .and(warp::get())
+        .and(warp::document::document(warp::document::description(
+            "Index",
+        )))
+        .map(|| "Index");
+
+    let hidden = warp::document::to_openapi(describe(
+        &index.clone().or(warp::filters::fallback(not_found_page)),
+    ));
+    assert_eq!(hidden.paths.keys().collect::<Vec<_>>(), vec!["/"]);
+
+    let routes = describe(&index.or(warp::filters::fallback(not_found_page).documented()));
+    let openapi = warp::document::to_openapi(routes);
+    assert_eq!(
+        openapi.paths.keys().collect::<Vec<_>>(),
+        vec!["/", "/{fallback_path}"]
+    );
+
+    let get = |path: &str| match &openapi.paths[path] {
+        ReferenceOr::Item(item) => item.get.clone().expect("documented as GET"),
+        ReferenceOr::Reference { .. } => panic!("unexpected reference"),
+    };
+    assert_eq!(get("/").description.as_deref(), Some("Index"));
+    let fallback = get("/{fallback_path}");
+    assert_eq!(
+        fallback.responses.responses.keys().collect::<Vec<_>>(),
+        vec![&StatusCode::Code(404)]
+    );
+}
+
+#[tokio::test]
+async fn recover_finds_rejection() {
+    use warp::filters::fallback::FallbackNotFound;
+
+    let routes = warp::path("hello")
+        .map(warp::reply)
+        .or(warp::filters::fallback(not_found_page))
+        .recover(|rejection: warp::Rejection| async move {
+            assert!(!rejection.is_not_found());
+            match rejection.find::<FallbackNotFound>() {
+                Some(fallback) => {
+                    let mut res = fallback.response();
+                    res.headers_mut()
+                        .insert("x-recovered", warp::http::HeaderValue::from_static("yes"));
+                    Ok(res)
+                }
+                None => Err(rejection),
+            }
+        });
+
+    let res = warp::test::request().path("/nope").reply(&routes).await;
+    assert_eq!(res.status(), 404);
+    assert_eq!(res.headers()["x-recovered"], "yes");
+    assert_eq!(res.body(), "<h1>Nothing to see here</h1>");
+}
@@ -0,0 +1,296 @@
+#![deny(warnings)]
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use futures::{future, stream, StreamExt};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use warp::filters::hooks::{Disposition, Hooks, RequestInfo, ResponseInfo};
+use warp::http::{Method, StatusCode};
+use warp::hyper::Body;
+use warp::Filter;
+
+type Finished = (String, Disposition, Option<StatusCode>);
+type Recorded = mpsc::UnboundedReceiver<Finished>;
+type OnRequest = fn(RequestInfo) -> String;
+
+fn recording() -> (
+    Hooks<OnRequest, impl Fn(String, ResponseInfo) + Clone>,
+    Recorded,
+) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let hooks = warp::filters::hooks(
+        (|req: RequestInfo| format!("{} {}", req.method(), req.path())) as OnRequest,
+        move |token: String, info: ResponseInfo| {
+            tx.send((token, info.disposition(), info.status()))
+                .expect("receiver alive");
+        },
+    );
+    (hooks, rx)
+}
+
+#[tokio::test]
+async fn completed() {
+    let _ = pretty_env_logger::try_init();
+
+    let (hooks, mut rx) = recording();
+    let route = warp::path("hello").map(|| "Hello, World!").with(hooks);
+
+    let res = warp::test::request().path("/hello").reply(&route).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.body(), "Hello, World!");
+
+    let finished = rx.recv().await.expect("on_response called");
+    assert_eq!(
+        finished,
+        (
+            "GET /hello".to_string(),
+            Disposition::Completed,
+            Some(StatusCode::OK)
+        )
+    );
+}
+
+// Reads one response from a kept-alive connection, returning its head and body.
+async fn read_response(stream: &mut tokio::net::TcpStream, head_only: bool) -> (String, Vec<u8>) {
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        let mut byte = [0; 1];
+        stream.read_exact(&mut byte).await.expect("read head");
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8(head).expect("utf8 head");
+
+    let mut body = Vec::new();
+    if head_only {
+        return (head, body);
+    }
+    if let Some(len) = head
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length: "))
+    {
+        body.resize(len.parse().expect("content-length"), 0);
+        stream.read_exact(&mut body).await.expect("read body");
+    } else {
+        assert!(head.contains("transfer-encoding: chunked\r\n"), "{}", head);
+        // Chunk sizes are read a line at a time, and then the chunk itself.
+        loop {
+            let mut line = Vec::new();
+            while !line.ends_with(b"\r\n") {
+                let mut byte = [0; 1];
+                stream.read_exact(&mut byte).await.expect("read chunk size");
+                line.push(byte[0]);
+            }
+            let size = std::str::from_utf8(&line).expect("utf8 chunk size").trim();
+            let size = usize::from_str_radix(size, 16).expect("chunk size");
+            let mut chunk = vec![0; size + 2];
+            stream.read_exact(&mut chunk).await.expect("read chunk");
+            if size == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..size]);
+        }
+    }
+    (head, body)
+}
+
+#[tokio::test]
+async fn completed_over_loopback() {
+    let _ = pretty_env_logger::try_init();
+
+    let (hooks, mut rx) = recording();
+    let hello = warp::path("hello").map(|| "Hello, World!");
+    // hyper stops polling a body once its own `content-length` has been sent.
+    let sized = warp::path("sized")
+        .map(|| warp::reply::with_header("Hello, World!", "content-length", "13"));
+    let route = hello.or(sized).with(hooks);
+    let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::task::spawn(server);
+
+    // The connection is kept open, so the body's end is never found by the
+    // connection closing.
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    for &(method, path) in &[
+        ("GET", "/hello"),
+        ("HEAD", "/hello"),
+        ("GET", "/sized"),
+        ("HEAD", "/sized"),
+    ] {
+        let req = format!("{} {} HTTP/1.1\r\nhost: localhost\r\n\r\n", method, path);
+        stream.write_all(req.as_bytes()).await.expect("write");
+
+        let (head, body) = read_response(&mut stream, method == "HEAD").await;
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "{}", head);
+        if method == "GET" {
+            assert_eq!(body, b"Hello, World!");
+        }
+
+        let finished = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("on_response called while connection is open")
+            .expect("on_response called");
+        assert_eq!(
+            finished,
+            (
+                format!("{} {}", method, path),
+                Disposition::Completed,
+                Some(StatusCode::OK)
+            )
+        );
+    }
+}
+
+// The stream decoder is deprecated, but is what `warp::compression` encodes with.
+#[cfg(feature = "compression")]
+#[allow(deprecated)]
+#[tokio::test]
+async fn completed_under_compression() {
+    use async_compression::stream::GzipDecoder;
+    use futures::TryStreamExt;
+
+    let _ = pretty_env_logger::try_init();
+
+    let (hooks, mut rx) = recording();
+    let route = warp::any()
+        .map(|| "hello world ".repeat(200))
+        .with(hooks)
+        .with(warp::compression::gzip());
+    let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::task::spawn(server);
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n\r\n")
+        .await
+        .expect("write");
+    let (head, body) = read_response(&mut stream, false).await;
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "{}", head);
+    assert!(head.contains("content-encoding: gzip\r\n"), "{}", head);
+
+    let body = stream::once(future::ok::<_, std::io::Error>(bytes::Bytes::from(body)));
+    let decoded = GzipDecoder::new(body)
+        .try_fold(Vec::new(), |mut decoded, chunk| async move {
+            decoded.extend_from_slice(&chunk);
+            Ok(decoded)
+        })
+        .await
+        .expect("gzip body");
+    assert_eq!(decoded, "hello world ".repeat(200).as_bytes());
+
+    let finished = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("on_response called")
+        .expect("on_response called");
+    assert_eq!(
+        finished,
+        (
+            "GET /".to_string(),
+            Disposition::Completed,
+            Some(StatusCode::OK)
+        )
+    );
+}
+
+#[tokio::test]
+async fn completed_empty_body() {
+    let _ = pretty_env_logger::try_init();
+
+    let (hooks, mut rx) = recording();
+    let route = warp::any().map(warp::reply).with(hooks);
+
+    let res = warp::test::request()
+        .method("DELETE")
+        .path("/thing")
+        .reply(&route)
+        .await;
+    assert_eq!(res.status(), 200);
+
+    let (token, disposition, status) = rx.recv().await.expect("on_response called");
+    assert_eq!(token, format!("{} /thing", Method::DELETE));
+    assert_eq!(disposition, Disposition::Completed);
+    assert_eq!(status, Some(StatusCode::OK));
+}
+
+#[tokio::test]
+async fn rejected() {
+    let _ = pretty_env_logger::try_init();
+
+    let (hooks, mut rx) = recording();
+    let route = warp::path("hello").map(warp::reply).with(hooks);
+
+    let res = warp::test::request().path("/nope").reply(&route).await;
+    assert_eq!(res.status(), 404);
+
+    let finished = rx.recv().await.expect("on_response called");
+    assert_eq!(
+        finished,
+        (
+            "GET /nope".to_string(),
+            Disposition::Rejected,
+            Some(StatusCode::NOT_FOUND)
+        )
+    );
+}
+
+#[tokio::test]
+async fn aborted_on_disconnect() {
+    let _ = pretty_env_logger::try_init();
+
+    let (hooks, mut rx) = recording();
+    // A body that sends one chunk, and then never finishes.
+    let route = warp::any()
+        .map(|| {
+            let chunks =
+                stream::once(future::ok::<_, Infallible>("first")).chain(stream::pending());
+            warp::http::Response::new(Body::wrap_stream(chunks))
+        })
+        .with(hooks);
+
+    let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::task::spawn(server);
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    stream
+        .write_all(b"GET /stream HTTP/1.1\r\nhost: localhost\r\n\r\n")
+        .await
+        .expect("write");
+    let mut buf = [0; 1024];
+    let n = stream.read(&mut buf).await.expect("read");
+    assert!(buf[..n].starts_with(b"HTTP/1.1 200 OK\r\n"));
+    drop(stream);
+
+    let finished = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .expect("on_response called after disconnect")
+        .expect("on_response called");
+    assert_eq!(
+        finished,
+        (
+            "GET /stream".to_string(),
+            Disposition::Aborted,
+            Some(StatusCode::OK)
+        )
+    );
+}
+
+#[tokio::test]
+async fn aborted_before_reply() {
+    let _ = pretty_env_logger::try_init();
+
+    let (hooks, mut rx) = recording();
+    let route = warp::any()
+        .and_then(future::pending::<Result<&'static str, warp::Rejection>>)
+        .with(hooks);
+
+    let req = warp::test::request().path("/slow").reply(&route);
+    tokio::time::timeout(Duration::from_millis(10), req)
+        .await
+        .expect_err("reply never finishes");
+
+    let finished = rx.recv().await.expect("on_response called");
+    assert_eq!(
+        finished,
+        ("GET /slow".to_string(), Disposition::Aborted, None)
+    );
+}
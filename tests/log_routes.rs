@@ -0,0 +1,66 @@
+#![deny(warnings)]
+
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use warp::Filter;
+
+// Keeps the messages logged by the server, to check what `log_routes` logged.
+struct Capture;
+
+static LOGGED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target() == "warp::server" {
+            let mut logged = LOGGED.lock().unwrap();
+            logged.push(format!("{} {}", record.level(), record.args()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture;
+
+fn take_logged() -> Vec<String> {
+    std::mem::take(&mut *LOGGED.lock().unwrap())
+}
+
+// Setting the logger and level is global, so this is a single test.
+#[test]
+fn log_routes() {
+    log::set_logger(&CAPTURE).expect("no other logger");
+
+    let hello = warp::get()
+        .and(warp::path("hello"))
+        .and(warp::document::document(warp::document::description(
+            "Say hello",
+        )))
+        .map(warp::reply);
+    let bye = warp::delete().and(warp::path("bye")).map(warp::reply);
+    let routes = hello.or(bye);
+
+    // Nothing is logged, or built, if `info` isn't enabled.
+    log::set_max_level(LevelFilter::Warn);
+    let _server = warp::serve(routes.clone()).log_routes();
+    assert!(take_logged().is_empty());
+
+    log::set_max_level(LevelFilter::Info);
+    let _server = warp::serve(routes.clone()).log_routes();
+    let table = warp::document::routes_table(&warp::document::summarize(&routes), false);
+    assert_eq!(
+        take_logged(),
+        vec![format!("{} routes:\n{}", Level::Info, table.trim_end())]
+    );
+    assert_eq!(
+        table,
+        "METHOD  PATH    SUMMARY    TAGS\n\
+         GET     /hello  Say hello\n\
+         DELETE  /bye\n"
+    );
+}
@@ -59,6 +59,76 @@ async fn param() {
     );
 }
 
+#[tokio::test]
+async fn param_optional() {
+    let _ = pretty_env_logger::try_init();
+
+    let report = warp::path("reports")
+        .and(warp::path::param::<u16>())
+        .and(warp::path::param_optional::<u8>())
+        .and(warp::path::end());
+
+    let req = warp::test::request().path("/reports/2020");
+    assert_eq!(req.filter(&report).await.unwrap(), (2020, None));
+
+    let req = warp::test::request().path("/reports/2020/");
+    assert_eq!(req.filter(&report).await.unwrap(), (2020, None));
+
+    let req = warp::test::request().path("/reports/2020/4");
+    assert_eq!(req.filter(&report).await.unwrap(), (2020, Some(4)));
+
+    // extra segments are left for end() to reject
+    let req = warp::test::request().path("/reports/2020/4/extra");
+    assert!(!req.matches(&report).await);
+}
+
+#[tokio::test]
+async fn param_optional_backtracks() {
+    let _ = pretty_env_logger::try_init();
+
+    let month = warp::path("reports")
+        .and(warp::path::param_optional::<u8>())
+        .and(warp::path::end())
+        .map(|month: Option<u8>| format!("month {:?}", month));
+    let latest = warp::path("reports")
+        .and(warp::path("latest"))
+        .and(warp::path::end())
+        .map(|| "latest".to_string());
+    let routes = month.or(latest).unify();
+
+    // a segment that fails to parse rejects, and the next route is tried
+    let req = warp::test::request().path("/reports/latest");
+    assert_eq!(req.filter(&routes).await.unwrap(), "latest");
+
+    let req = warp::test::request().path("/reports/4");
+    assert_eq!(req.filter(&routes).await.unwrap(), "month Some(4)");
+
+    let req = warp::test::request().path("/reports");
+    assert_eq!(req.filter(&routes).await.unwrap(), "month None");
+
+    let req = warp::test::request().path("/reports/nope");
+    assert!(!req.matches(&routes).await);
+
+    let res = warp::test::request()
+        .path("/reports/nope")
+        .reply(&routes)
+        .await;
+    assert_eq!(res.status(), 404);
+}
+
+#[test]
+fn param_optional_describe() {
+    let route = warp::path("reports")
+        .and(warp::path::param::<u16>())
+        .and(warp::path::param_optional::<u8>())
+        .and(warp::path::end());
+
+    let docs = warp::document::describe(&route);
+    let paths = docs.iter().map(|r| r.pretty_path()).collect::<Vec<_>>();
+    assert_eq!(paths, ["/reports/{param1}", "/reports/{param1}/{param2}"]);
+    assert!(docs[1].parameters.iter().all(|param| param.required));
+}
+
 #[tokio::test]
 async fn end() {
     let _ = pretty_env_logger::try_init();
@@ -121,3 +121,20 @@ async fn raw_query() {
     let extracted = req.filter(&as_raw).await.unwrap();
     assert_eq!(extracted, "foo=bar&baz=quux".to_owned());
 }
+
+#[tokio::test]
+async fn max_length() {
+    let limited = warp::query::max_length(16).and(warp::query::raw());
+
+    let req = warp::test::request().path("/?foo=bar&baz=quux");
+    assert_eq!(req.filter(&limited).await.unwrap(), "foo=bar&baz=quux");
+
+    let req = warp::test::request().path("/");
+    assert!(req.matches(&warp::query::max_length(16)).await);
+
+    let res = warp::test::request()
+        .path("/?foo=bar&baz=quuux")
+        .reply(&limited)
+        .await;
+    assert_eq!(res.status(), 414);
+}
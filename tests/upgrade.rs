@@ -0,0 +1,116 @@
+#![deny(warnings)]
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use warp::filters::upgrade::{Upgrade, Upgraded};
+use warp::Filter;
+
+#[tokio::test]
+async fn upgrade() {
+    let _ = pretty_env_logger::try_init();
+
+    let route = warp::filters::upgrade::on("echo").map(|up: Upgrade| up.on_upgrade(|_| async {}));
+
+    let resp = warp::test::request()
+        .header("connection", "upgrade")
+        .header("upgrade", "echo")
+        .reply(&route)
+        .await;
+
+    assert_eq!(resp.status(), 101);
+    assert_eq!(resp.headers()["connection"], "upgrade");
+    assert_eq!(resp.headers()["upgrade"], "echo");
+
+    let resp = warp::test::request()
+        .method("POST")
+        .header("connection", "keep-alive, Upgrade")
+        .header("upgrade", "ECHO")
+        .reply(&route)
+        .await;
+
+    assert_eq!(resp.status(), 101);
+}
+
+#[tokio::test]
+async fn reject_without_upgrade_headers() {
+    let _ = pretty_env_logger::try_init();
+
+    let route = warp::filters::upgrade::on("echo").map(|up: Upgrade| up.on_upgrade(|_| async {}));
+
+    let req = warp::test::request();
+    assert!(!req.matches(&route).await);
+
+    let req = warp::test::request().header("connection", "upgrade");
+    assert!(!req.matches(&route).await);
+
+    let req = warp::test::request()
+        .header("connection", "keep-alive")
+        .header("upgrade", "echo");
+    assert!(!req.matches(&route).await);
+
+    let req = warp::test::request()
+        .header("connection", "upgrade")
+        .header("upgrade", "websocket");
+    assert!(!req.matches(&route).await);
+}
+
+#[tokio::test]
+async fn coexists_with_normal_routes() {
+    let _ = pretty_env_logger::try_init();
+
+    let upgrade = warp::filters::upgrade::on("echo")
+        .map(|up: Upgrade| up.on_upgrade(|_| async {}))
+        .map(warp::reply::into_response);
+    let normal = warp::any().map(|| "plain").map(warp::reply::into_response);
+    let route = upgrade.or(normal).unify();
+
+    let resp = warp::test::request().reply(&route).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.body(), "plain");
+
+    let resp = warp::test::request()
+        .header("connection", "upgrade")
+        .header("upgrade", "echo")
+        .reply(&route)
+        .await;
+    assert_eq!(resp.status(), 101);
+}
+
+#[tokio::test]
+async fn echo_over_loopback() {
+    let _ = pretty_env_logger::try_init();
+
+    let route = warp::filters::upgrade::on("echo").map(|up: Upgrade| {
+        up.on_upgrade(|upgraded: Upgraded| async move {
+            let (mut rd, mut wr) = tokio::io::split(upgraded);
+            let _ = tokio::io::copy(&mut rd, &mut wr).await;
+        })
+    });
+    let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::task::spawn(server);
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    stream
+        .write_all(
+            b"GET / HTTP/1.1\r\nhost: localhost\r\nconnection: upgrade\r\nupgrade: echo\r\n\r\n",
+        )
+        .await
+        .expect("write handshake");
+
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        let mut byte = [0; 1];
+        stream.read_exact(&mut byte).await.expect("read handshake");
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8(head).expect("utf8 head");
+    assert!(
+        head.starts_with("HTTP/1.1 101 Switching Protocols\r\n"),
+        "{}",
+        head
+    );
+
+    stream.write_all(b"hello warp").await.expect("write");
+    let mut buf = [0; 10];
+    stream.read_exact(&mut buf).await.expect("read");
+    assert_eq!(&buf, b"hello warp");
+}
@@ -0,0 +1,188 @@
+#![deny(warnings)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use warp::Filter;
+
+// Counts every byte allocated, so tests can check how much a request costs.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+// The allocation counter is global, so tests in this file run one at a time,
+// each on its own runtime.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial<F: Future>(test: F) -> F::Output {
+    let _guard = SERIAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    tokio::runtime::Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .expect("runtime")
+        .block_on(test)
+}
+
+fn request(target: &str) -> Vec<u8> {
+    format!(
+        "GET {} HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n",
+        target
+    )
+    .into_bytes()
+}
+
+async fn send(addr: SocketAddr, req: &[u8]) -> String {
+    let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+    // The server may reply and close before reading all of a huge request.
+    let _ = stream.write_all(req).await;
+
+    let mut res = Vec::new();
+    let _ = stream.read_to_end(&mut res).await;
+    String::from_utf8_lossy(&res).into_owned()
+}
+
+#[test]
+fn query_max_length_skips_decoding() {
+    serial(async {
+        let _ = pretty_env_logger::try_init();
+
+        let route = warp::query::max_length(1024)
+            .and(warp::query::raw())
+            .map(|query: String| query);
+
+        let path = format!("/?q={}", "a".repeat(60 * 1024));
+        let req = warp::test::request().path(&path);
+
+        let before = ALLOCATED.load(Ordering::SeqCst);
+        let res = req.reply(&route).await;
+        let allocated = ALLOCATED.load(Ordering::SeqCst) - before;
+
+        assert_eq!(res.status(), 414);
+        assert!(
+            allocated < 16 * 1024,
+            "allocated {} bytes for a rejected query",
+            allocated
+        );
+    })
+}
+
+// hyper answers a request head larger than its read buffer by itself, before
+// warp sees the request. This checks that doesn't change, as `max_uri_length`
+// relies on it to bound the memory used by URIs past the buffer's size.
+#[test]
+fn server_huge_query_rejected_by_hyper() {
+    serial(async {
+        let _ = pretty_env_logger::try_init();
+
+        let route = warp::query::raw().map(|query: String| query);
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::task::spawn(server);
+
+        let req = request(&format!("/?q={}", "a".repeat(10 * 1024 * 1024)));
+
+        let before = ALLOCATED.load(Ordering::SeqCst);
+        let res = send(addr, &req).await;
+        let allocated = ALLOCATED.load(Ordering::SeqCst) - before;
+
+        assert!(
+            res.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"),
+            "{}",
+            res
+        );
+        assert!(
+            allocated < 4 * 1024 * 1024,
+            "allocated {} bytes for a 10mb query",
+            allocated
+        );
+    })
+}
+
+// URIs over `max_uri_length` but within hyper's read buffer are only
+// rejected by warp, before any filter decodes them.
+#[test]
+fn server_bounds_memory_for_long_query() {
+    serial(async {
+        let _ = pretty_env_logger::try_init();
+
+        let route = warp::query::<HashMap<String, String>>()
+            .map(|query: HashMap<String, String>| format!("{}", query.len()));
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::task::spawn(server);
+
+        let query = (0..4 * 1024)
+            .map(|i| format!("k{:05}=v", i))
+            .collect::<Vec<_>>()
+            .join("&");
+        let req = request(&format!("/?{}", query));
+        assert!(req.len() > 32 * 1024);
+
+        let before = ALLOCATED.load(Ordering::SeqCst);
+        let res = send(addr, &req).await;
+        let allocated = ALLOCATED.load(Ordering::SeqCst) - before;
+
+        assert!(res.starts_with("HTTP/1.1 414 URI Too Long\r\n"), "{}", res);
+        assert!(
+            allocated < 256 * 1024,
+            "allocated {} bytes for a {} byte query",
+            allocated,
+            query.len()
+        );
+    })
+}
+
+#[test]
+fn server_max_uri_length() {
+    serial(async {
+        let _ = pretty_env_logger::try_init();
+
+        let route = warp::any().map(|| -> &'static str {
+            panic!("filters shouldn't run for long URIs");
+        });
+        let (addr, server) = warp::serve(route)
+            .max_uri_length(1024)
+            .bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::task::spawn(server);
+
+        let res = send(addr, &request(&format!("/?q={}", "a".repeat(2048)))).await;
+        assert!(res.starts_with("HTTP/1.1 414 URI Too Long\r\n"), "{}", res);
+    })
+}
+
+#[test]
+fn server_default_max_uri_length() {
+    serial(async {
+        let _ = pretty_env_logger::try_init();
+
+        let route = warp::any().map(warp::reply);
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::task::spawn(server);
+
+        let res = send(addr, &request("/?q=short")).await;
+        assert!(res.starts_with("HTTP/1.1 200 OK\r\n"), "{}", res);
+
+        let res = send(addr, &request(&format!("/{}", "a".repeat(32 * 1024)))).await;
+        assert!(res.starts_with("HTTP/1.1 414 URI Too Long\r\n"), "{}", res);
+    })
+}